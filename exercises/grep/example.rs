@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::fs;
+
+/// Flags-related logic for the `grep` exercise.
+///
+/// Mirrors the subset of `grep(1)` switches the exercise cares about. The same
+/// struct drives both the single-pattern [`grep`] and the multi-pattern
+/// [`grep_many`] entry points.
+#[derive(Debug, Default)]
+pub struct Flags {
+    line_numbers: bool,
+    file_names: bool,
+    insensitive: bool,
+    whole_line: bool,
+    inverted: bool,
+}
+
+impl Flags {
+    pub fn new(flags: &[&str]) -> Self {
+        let mut parsed = Flags::default();
+
+        for flag in flags {
+            match *flag {
+                "-n" => parsed.line_numbers = true,
+                "-l" => parsed.file_names = true,
+                "-i" => parsed.insensitive = true,
+                "-x" => parsed.whole_line = true,
+                "-v" => parsed.inverted = true,
+                _ => {}
+            }
+        }
+
+        parsed
+    }
+
+    fn normalize(&self, line: &str) -> String {
+        if self.insensitive {
+            line.to_lowercase()
+        } else {
+            line.to_string()
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FileAccessError {
+    #[error("File not found: {file_name}")]
+    FileNotFoundError { file_name: String },
+    #[error("Error reading file: {file_name}")]
+    FileReadError { file_name: String },
+}
+
+pub fn grep(pattern: &str, flags: &Flags, files: &[&str]) -> Result<Vec<String>, FileAccessError> {
+    grep_many(&[pattern], flags, files)
+}
+
+/// Search `files` for *any* of the given literal `patterns` in a single pass
+/// over each line, honouring the same `-l`/`-n`/`-i`/`-x`/`-v` flag semantics as
+/// [`grep`].
+///
+/// Rather than looping over each line once per pattern, this builds an
+/// [Aho-Corasick][wiki] automaton from all patterns up front and walks every
+/// line through it exactly once, so the scan stays linear in the length of the
+/// text regardless of how many patterns are supplied. With `-x` the automaton
+/// is not used: each line is compared for equality against every pattern.
+///
+/// [wiki]: https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm
+pub fn grep_many(
+    patterns: &[&str],
+    flags: &Flags,
+    files: &[&str],
+) -> Result<Vec<String>, FileAccessError> {
+    let needles: Vec<String> = patterns.iter().map(|p| flags.normalize(p)).collect();
+    let automaton = Automaton::new(&needles);
+
+    let print_file_names = files.len() > 1;
+    let mut matches = Vec::new();
+
+    for file_name in files {
+        let contents = fs::read_to_string(file_name).map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => FileAccessError::FileNotFoundError {
+                file_name: file_name.to_string(),
+            },
+            _ => FileAccessError::FileReadError {
+                file_name: file_name.to_string(),
+            },
+        })?;
+
+        for (index, line) in contents.lines().enumerate() {
+            let haystack = flags.normalize(line);
+
+            let found = if flags.whole_line {
+                needles.iter().any(|needle| *needle == haystack)
+            } else {
+                automaton.matches(&haystack)
+            };
+
+            if found == flags.inverted {
+                continue;
+            }
+
+            if flags.file_names {
+                matches.push(file_name.to_string());
+                break;
+            }
+
+            let mut rendered = String::new();
+
+            if print_file_names {
+                rendered.push_str(&format!("{}:", file_name));
+            }
+
+            if flags.line_numbers {
+                rendered.push_str(&format!("{}:", index + 1));
+            }
+
+            rendered.push_str(line);
+            matches.push(rendered);
+        }
+    }
+
+    Ok(matches)
+}
+
+/// An Aho-Corasick automaton over a set of literal byte patterns.
+///
+/// Each node owns a sparse map from byte to child index, a failure link to the
+/// node reached by the longest proper suffix that is itself in the trie, and a
+/// flag marking whether any pattern ends at (or through the failure chain of)
+/// that node.
+struct Automaton {
+    children: Vec<std::collections::HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    output: Vec<bool>,
+}
+
+impl Automaton {
+    fn new(patterns: &[String]) -> Self {
+        let mut automaton = Automaton {
+            children: vec![std::collections::HashMap::new()],
+            fail: vec![0],
+            output: vec![false],
+        };
+
+        for pattern in patterns {
+            automaton.insert(pattern.as_bytes());
+        }
+
+        automaton.build_failure_links();
+        automaton
+    }
+
+    fn insert(&mut self, pattern: &[u8]) {
+        let mut node = 0;
+
+        for &byte in pattern {
+            node = match self.children[node].get(&byte) {
+                Some(&next) => next,
+                None => {
+                    let next = self.children.len();
+                    self.children.push(std::collections::HashMap::new());
+                    self.fail.push(0);
+                    self.output.push(false);
+                    self.children[node].insert(byte, next);
+                    next
+                }
+            };
+        }
+
+        self.output[node] = true;
+    }
+
+    // BFS over the trie computing a failure link for every node and folding the
+    // output of the failure target into each node's own output set.
+    fn build_failure_links(&mut self) {
+        let mut queue = VecDeque::new();
+
+        let root_children: Vec<usize> = self.children[0].values().copied().collect();
+        for child in root_children {
+            self.fail[child] = 0;
+            queue.push_back(child);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> =
+                self.children[node].iter().map(|(&b, &c)| (b, c)).collect();
+
+            for (byte, child) in edges {
+                let mut fallback = self.fail[node];
+
+                while fallback != 0 && !self.children[fallback].contains_key(&byte) {
+                    fallback = self.fail[fallback];
+                }
+
+                let link = match self.children[fallback].get(&byte) {
+                    Some(&target) if target != child => target,
+                    _ => 0,
+                };
+
+                self.fail[child] = link;
+                self.output[child] = self.output[child] || self.output[link];
+                queue.push_back(child);
+            }
+        }
+    }
+
+    // Walk a single state pointer through `text`, following failure links
+    // whenever there is no matching child, and report a hit as soon as the
+    // current node carries a non-empty output set.
+    fn matches(&self, text: &str) -> bool {
+        if self.output[0] {
+            return true;
+        }
+
+        let mut node = 0;
+
+        for &byte in text.as_bytes() {
+            while node != 0 && !self.children[node].contains_key(&byte) {
+                node = self.fail[node];
+            }
+
+            if let Some(&next) = self.children[node].get(&byte) {
+                node = next;
+            }
+
+            if self.output[node] {
+                return true;
+            }
+        }
+
+        false
+    }
+}
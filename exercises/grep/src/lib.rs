@@ -33,3 +33,25 @@ pub fn grep(pattern: &str, flags: &Flags, files: &[&str]) -> Result<Vec<String>,
         flags
     );
 }
+
+/// Search `files` for *any* of the given literal `patterns` in a single pass over
+/// each line, honouring the same `-l`/`-n`/`-i`/`-x`/`-v` flag semantics as [`grep`].
+///
+/// Rather than looping over each line once per pattern, this builds an
+/// [Aho-Corasick][wiki] automaton from all patterns up front and walks every line
+/// through it exactly once, so the scan stays linear in the length of the text
+/// regardless of how many patterns are supplied.
+///
+/// [wiki]: https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm
+pub fn grep_many(
+    patterns: &[&str],
+    flags: &Flags,
+    files: &[&str],
+) -> Result<Vec<String>, FileAccessError> {
+    unimplemented!(
+        "Search the files '{:?}' for any of the patterns '{:?}' in a single pass, honouring the flags '{:?}'",
+        files,
+        patterns,
+        flags
+    );
+}
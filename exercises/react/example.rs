@@ -1,73 +1,213 @@
 // TODO: For now, lib is symlinked to example to ease local development.
 // But the final plan is to provide a stub file once we know what the interface will be.
 
-pub trait Cell<T> {
-    fn value(&self) -> &T;
-}
+use std::collections::HashMap;
+
+/// Handle identifying an input cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputCellId(usize);
+
+/// Handle identifying a compute cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ComputeCellId(usize);
 
-trait Propagatable {
-    fn propagate(&mut self);
+/// Handle identifying a callback registered on a compute cell, used to cancel it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CallbackId(usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CellId {
+    Input(InputCellId),
+    Compute(ComputeCellId),
 }
 
-pub struct Reactor {
-    cells: Vec<Box<Propagatable>>,
+#[derive(Debug, PartialEq, Eq)]
+pub enum RemoveCallbackError {
+    NonexistentCell,
+    NonexistentCallback,
 }
 
-pub struct InputCell<T> {
-    val: T,
+struct ComputeCell<'a, T> {
+    dependencies: Vec<CellId>,
+    compute_func: Box<dyn Fn(&[T]) -> T + 'a>,
+    value: T,
+    callbacks: HashMap<usize, Box<dyn FnMut(T) + 'a>>,
+    next_callback: usize,
 }
 
-pub struct Compute1Cell<'a, T: 'a, U, F: Fn(&T) -> U> {
-    compute: F,
-    cell: &'a Cell<T>,
-    val: U,
+/// A spreadsheet-like network of input and compute cells.
+///
+/// Cells form a dependency DAG: a compute cell may only depend on cells created
+/// before it, so ascending creation order is itself a valid topological order.
+/// Setting an input value recomputes every affected compute cell in that order,
+/// so no cell is recomputed before its dependencies have settled, and callbacks
+/// observe only the stabilized result.
+pub struct Reactor<'a, T> {
+    inputs: Vec<T>,
+    computes: Vec<ComputeCell<'a, T>>,
 }
 
-impl Reactor {
-    pub fn new() -> Reactor {
-        Reactor{
-            cells: Vec::new(),
+impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
+    pub fn new() -> Self {
+        Reactor {
+            inputs: Vec::new(),
+            computes: Vec::new(),
         }
     }
 
-    pub fn create_input<T>(&self, initial: T) -> InputCell<T> {
-        InputCell {
-            val: initial,
+    pub fn create_input(&mut self, initial: T) -> InputCellId {
+        self.inputs.push(initial);
+        InputCellId(self.inputs.len() - 1)
+    }
+
+    /// Create a compute cell over a single dependency.
+    pub fn create_compute1<F: Fn(T) -> T + 'a>(
+        &mut self,
+        dep: CellId,
+        compute_func: F,
+    ) -> Result<ComputeCellId, CellId> {
+        self.create_compute(&[dep], move |values| compute_func(values[0]))
+    }
+
+    /// Create a compute cell over two dependencies.
+    pub fn create_compute2<F: Fn(T, T) -> T + 'a>(
+        &mut self,
+        dep1: CellId,
+        dep2: CellId,
+        compute_func: F,
+    ) -> Result<ComputeCellId, CellId> {
+        self.create_compute(&[dep1, dep2], move |values| {
+            compute_func(values[0], values[1])
+        })
+    }
+
+    /// Create a compute cell over an arbitrary number of dependencies.
+    ///
+    /// Fails with the offending [`CellId`] if any dependency does not exist.
+    pub fn create_compute<F: Fn(&[T]) -> T + 'a>(
+        &mut self,
+        dependencies: &[CellId],
+        compute_func: F,
+    ) -> Result<ComputeCellId, CellId> {
+        for &dependency in dependencies {
+            if self.value(dependency).is_none() {
+                return Err(dependency);
+            }
         }
+
+        let compute_func: Box<dyn Fn(&[T]) -> T + 'a> = Box::new(compute_func);
+
+        let values: Vec<T> = dependencies
+            .iter()
+            .map(|&dependency| {
+                self.value(dependency)
+                    .expect("dependency existence checked above")
+            })
+            .collect();
+
+        let value = compute_func(&values);
+
+        self.computes.push(ComputeCell {
+            dependencies: dependencies.to_vec(),
+            compute_func,
+            value,
+            callbacks: HashMap::new(),
+            next_callback: 0,
+        });
+
+        Ok(ComputeCellId(self.computes.len() - 1))
     }
 
-    pub fn create_compute1<'a, T, U, F>(&mut self, cell: &'a Cell<T>, compute: F) -> Compute1Cell<'a, T, U, F>
-        where F: Fn(&T) -> U {
-        let cell = Compute1Cell {
-            val: compute(cell.value()),
-            cell: cell,
-            compute: compute,
-        };
-        self.cells.push(Box::new(cell));
-        cell
+    pub fn value(&self, id: CellId) -> Option<T> {
+        match id {
+            CellId::Input(InputCellId(index)) => self.inputs.get(index).copied(),
+            CellId::Compute(ComputeCellId(index)) => self.computes.get(index).map(|c| c.value),
+        }
     }
-}
 
-impl <T> Cell<T> for InputCell<T> {
-    fn value(&self) -> &T {
-        &self.val
+    /// Set an input cell's value and stabilize the network.
+    ///
+    /// Returns `false` if the id is unknown. Each compute cell is recomputed in
+    /// topological order; once stable, a callback fires at most once, and only
+    /// for cells whose final value differs from their value before the update.
+    pub fn set_value(&mut self, id: InputCellId, new_value: T) -> bool {
+        let InputCellId(index) = id;
+
+        if index >= self.inputs.len() {
+            return false;
+        }
+
+        let previous: Vec<T> = self.computes.iter().map(|c| c.value).collect();
+
+        self.inputs[index] = new_value;
+
+        for i in 0..self.computes.len() {
+            let value = self.eval_compute(i);
+            self.computes[i].value = value;
+        }
+
+        for i in 0..self.computes.len() {
+            if self.computes[i].value != previous[i] {
+                let value = self.computes[i].value;
+
+                for callback in self.computes[i].callbacks.values_mut() {
+                    callback(value);
+                }
+            }
+        }
+
+        true
     }
-}
 
-impl <T> InputCell<T> {
-    pub fn set_value(&mut self, new_val: T) {
-        self.val = new_val;
+    /// Register a callback fired with the cell's stabilized value whenever it
+    /// changes. Returns `None` if the cell does not exist.
+    pub fn add_callback<F: FnMut(T) + 'a>(
+        &mut self,
+        id: ComputeCellId,
+        callback: F,
+    ) -> Option<CallbackId> {
+        let cell = self.computes.get_mut(id.0)?;
+
+        let callback_id = cell.next_callback;
+        cell.next_callback += 1;
+        cell.callbacks.insert(callback_id, Box::new(callback));
+
+        Some(CallbackId(callback_id))
     }
-}
 
-impl <'a, T, U, F: Fn(&T) -> U> Cell<U> for Compute1Cell<'a, T, U, F> {
-    fn value(&self) -> &U {
-        &self.val
+    /// Cancel a previously-registered callback so it fires no more.
+    pub fn remove_callback(
+        &mut self,
+        cell: ComputeCellId,
+        callback: CallbackId,
+    ) -> Result<(), RemoveCallbackError> {
+        let cell = self
+            .computes
+            .get_mut(cell.0)
+            .ok_or(RemoveCallbackError::NonexistentCell)?;
+
+        cell.callbacks
+            .remove(&callback.0)
+            .map(drop)
+            .ok_or(RemoveCallbackError::NonexistentCallback)?;
+
+        Ok(())
     }
-}
 
-impl <'a, T, U, F: Fn(&T) -> U> Propagatable for Compute1Cell<'a, T, U, F> {
-    fn propagate(&mut self) {
-        self.val = (self.compute)(self.cell.value());
+    // Evaluate a compute cell from its dependencies' current values. Safe to
+    // call while stabilizing because dependencies always precede the cell in
+    // creation order and have therefore already settled.
+    fn eval_compute(&self, index: usize) -> T {
+        let dependencies = self.computes[index].dependencies.clone();
+
+        let values: Vec<T> = dependencies
+            .iter()
+            .map(|&dependency| {
+                self.value(dependency)
+                    .expect("dependency validated at creation time")
+            })
+            .collect();
+
+        (self.computes[index].compute_func)(&values)
     }
 }
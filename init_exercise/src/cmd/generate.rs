@@ -10,6 +10,8 @@ use std::{
 };
 use toml::Value as TomlValue;
 
+use crate::errors::{ChainErr, Error, Result};
+
 static GITIGNORE_CONTENT: &'static str = "# Generated by Cargo
 # will have compiled files and executables
 /target/
@@ -32,33 +34,147 @@ static EXAMPLE_RS_CONTENT: &'static str = "//! Example implementation
 //! - Test your example by running `../../bin/test-exercise`
 ";
 
-// Try to get the canonical data for the exercise of the given name
-fn get_canonical_data(exercise_name: &str) -> Option<JsonValue> {
+// Resolve the commit hash the local problem-specifications clone is checked out
+// at, used to key (and stale-check) the on-disk cache.
+fn problem_specifications_commit(problem_specifications_path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(problem_specifications_path)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Directory under the track root holding cached canonical-data.json payloads.
+fn cache_dir(track_root: &Path) -> std::path::PathBuf {
+    track_root.join(".cache").join("problem-specifications")
+}
+
+// Cache file for an exercise at a given problem-specifications commit. Keying on
+// the commit means a problem-specifications bump naturally invalidates the
+// previous payload.
+fn cache_path(track_root: &Path, exercise_name: &str, commit: &str) -> std::path::PathBuf {
+    cache_dir(track_root).join(format!("{}-{}.json", exercise_name, commit))
+}
+
+// Persist a raw canonical-data.json payload into the cache.
+fn write_cache(track_root: &Path, exercise_name: &str, commit: &str, content: &str) -> Result<()> {
+    ::std::fs::create_dir_all(cache_dir(track_root)).chain_err(|| "creating the cache directory")?;
+
+    ::std::fs::write(cache_path(track_root, exercise_name, commit), content)
+        .chain_err(|| "writing the canonical-data cache")
+}
+
+// Try to get the canonical data for the exercise of the given name.
+//
+// Resolution order is offline-first: the local problem-specifications clone is
+// consulted before the on-disk cache, and the network is only touched as a last
+// resort. Fetched payloads are written through to a commit-keyed cache so
+// repeated invocations are reproducible. When `offline` is set no network
+// access is permitted and a missing local/cached copy is a clean error.
+//
+// A missing exercise (HTTP 404) yields `Ok(None)` so the caller can fall back to
+// the standard template; an unreachable network is a user-facing failure, while
+// a malformed payload is an internal one.
+fn get_canonical_data(
+    exercise_name: &str,
+    problem_specifications_path: &Path,
+    track_root: &Path,
+    offline: bool,
+) -> Result<Option<JsonValue>> {
+    let commit = problem_specifications_commit(problem_specifications_path);
+
+    // 1. Prefer the local problem-specifications clone, write-through to cache.
+    let local_path = problem_specifications_path
+        .join("exercises")
+        .join(exercise_name)
+        .join("canonical-data.json");
+
+    if local_path.exists() {
+        let content = ::std::fs::read_to_string(&local_path)
+            .chain_err(|| "reading the local canonical-data.json")?;
+
+        let data = serde_json::from_str(&content)
+            .chain_err(|| "parsing the local canonical-data.json")?;
+
+        if let Some(commit) = &commit {
+            write_cache(track_root, exercise_name, commit, &content)?;
+        }
+
+        return Ok(Some(data));
+    }
+
+    // 2. Fall back to a cached payload for the current commit.
+    if let Some(commit) = &commit {
+        let cached = cache_path(track_root, exercise_name, commit);
+
+        if cached.exists() {
+            let content = ::std::fs::read_to_string(&cached)
+                .chain_err(|| "reading the cached canonical-data.json")?;
+
+            let data = serde_json::from_str(&content)
+                .chain_err(|| "parsing the cached canonical-data.json")?;
+
+            return Ok(Some(data));
+        }
+    }
+
+    // 3. Offline mode forbids any network access.
+    if offline {
+        return Err(Error::human(format!(
+            "canonical data for '{}' is not cached and no local problem-specifications clone is present, but --offline was given",
+            exercise_name
+        )));
+    }
+
+    // 4. Last resort: fetch over HTTP and write through to the cache.
     let url = format!("https://raw.githubusercontent.com/exercism/problem-specifications/master/exercises/{}/canonical-data.json", exercise_name);
 
-    let mut response =
-        reqwest::get(&url).expect("Failed to make HTTP request for the canonical data.");
+    let mut response = reqwest::get(&url)
+        .map_err(|_| Error::human(format!("could not reach problem-specifications at {}", url)))?;
+
+    if response.status() == StatusCode::NotFound {
+        return Ok(None);
+    }
 
     if response.status() != StatusCode::Ok {
-        return None;
-    } else {
-        return Some(
-            response
-                .json()
-                .expect("Failed to parse the JSON canonical-data response"),
-        );
+        return Err(Error::human(format!(
+            "unexpected status {} fetching canonical data for '{}'",
+            response.status(),
+            exercise_name
+        )));
+    }
+
+    let content = response
+        .text()
+        .chain_err(|| "reading the canonical-data.json response")?;
+
+    let data = serde_json::from_str(&content)
+        .chain_err(|| "parsing the canonical-data.json response")?;
+
+    if let Some(commit) = &commit {
+        write_cache(track_root, exercise_name, commit, &content)?;
     }
+
+    Ok(Some(data))
 }
 
 // Generate .meta directory and it's contents without using the canonical data
-fn generate_default_meta(exercise_name: &str, exercise_path: &Path) {
+fn generate_default_meta(exercise_name: &str, exercise_path: &Path) -> Result<()> {
     ::std::fs::create_dir(exercise_path.join(".meta"))
-        .expect("Failed to create the .meta directory");
+        .chain_err(|| "creating the .meta directory")?;
 
     ::std::fs::write(
         exercise_path.join(".meta").join("description.md"),
         "Describe your exercise here.\n\nDon't forget that `README.md` is automatically generated; update this within `.meta/description.md`.",
-    ).expect("Failed to create .meta/description.md file");
+    ).chain_err(|| "writing .meta/description.md")?;
 
     ::std::fs::write(
         exercise_path.join(".meta").join("metadata.yml"),
@@ -66,7 +182,7 @@ fn generate_default_meta(exercise_name: &str, exercise_path: &Path) {
             "---\nblurb: \"{}\"\nsource: \"\"\nsource_url: \"\"",
             exercise_name
         ),
-    ).expect("Failed to create .meta/metadata.yml file");
+    ).chain_err(|| "writing .meta/metadata.yml")?;
 
     let mut tests_file = OpenOptions::new()
         .append(true)
@@ -75,25 +191,38 @@ fn generate_default_meta(exercise_name: &str, exercise_path: &Path) {
                 .join("tests")
                 .join(format!("{}.rs", exercise_name)),
         )
-        .unwrap();
+        .chain_err(|| "opening the generated tests file")?;
 
-    tests_file.write(b"// Add your tests here").unwrap();
+    tests_file
+        .write_all(b"// Add your tests here")
+        .chain_err(|| "writing the tests-file placeholder")?;
+
+    Ok(())
 }
 
 // Update Cargo.toml of the generated exercise according to the fetched canonical data
-fn update_cargo_toml(exercise_name: &str, exercise_path: &Path, canonical_data: &JsonValue) {
+fn update_cargo_toml(
+    exercise_name: &str,
+    exercise_path: &Path,
+    canonical_data: &JsonValue,
+) -> Result<()> {
     let cargo_toml_content = ::std::fs::read_to_string(exercise_path.join("Cargo.toml"))
-        .expect("Error reading Cargo.toml");
+        .chain_err(|| "reading Cargo.toml")?;
 
-    let mut cargo_toml: TomlValue = cargo_toml_content.parse().unwrap();
+    let mut cargo_toml: TomlValue = cargo_toml_content
+        .parse()
+        .chain_err(|| "parsing Cargo.toml")?;
 
     {
-        let mut package_table = (&mut cargo_toml["package"]).as_table_mut().unwrap();
+        let package_table = cargo_toml["package"]
+            .as_table_mut()
+            .ok_or_else(|| Error::internal("Cargo.toml is missing its [package] table"))?;
 
-        package_table.insert(
-            "version".to_string(),
-            TomlValue::String(canonical_data["version"].as_str().unwrap().to_string()),
-        );
+        let version = canonical_data["version"].as_str().ok_or_else(|| {
+            Error::human("canonical data is missing a string 'version' field")
+        })?;
+
+        package_table.insert("version".to_string(), TomlValue::String(version.to_string()));
 
         package_table.insert(
             "name".to_string(),
@@ -102,7 +231,9 @@ fn update_cargo_toml(exercise_name: &str, exercise_path: &Path, canonical_data:
     }
 
     ::std::fs::write(exercise_path.join("Cargo.toml"), cargo_toml.to_string())
-        .expect("Failed to update Cargo.toml file");
+        .chain_err(|| "writing the updated Cargo.toml")?;
+
+    Ok(())
 }
 
 // Generate test suite using the canonical data
@@ -110,30 +241,38 @@ fn generate_tests_from_canonical_data(
     exercise_name: &str,
     exercise_path: &Path,
     canonical_data: &JsonValue,
-    use_maplit: bool,
-) {
-    update_cargo_toml(exercise_name, exercise_path, canonical_data);
+    _use_maplit: bool,
+) -> Result<()> {
+    update_cargo_toml(exercise_name, exercise_path, canonical_data)
+        .chain_err(|| format!("generating tests for '{}'", exercise_name))
 }
 
 // Generate a new exercise with specified name and flags
-fn generate_exercise(exercise_name: &str, run_configure: bool, use_maplit: bool) {
+fn generate_exercise(
+    exercise_name: &str,
+    run_configure: bool,
+    use_maplit: bool,
+    offline: bool,
+) -> Result<()> {
     let rev_parse_output = Command::new("git")
         .arg("rev-parse")
         .arg("--show-toplevel")
         .output()
-        .expect("Failed to get the path to the track repo.");
+        .chain_err(|| "running 'git rev-parse --show-toplevel'")?;
 
-    let track_root = String::from_utf8(rev_parse_output.stdout).unwrap();
+    let track_root = String::from_utf8(rev_parse_output.stdout)
+        .chain_err(|| "decoding the track root path")?;
+    let track_root = Path::new(track_root.trim()).to_path_buf();
 
-    let exercise_path = Path::new(&track_root.trim())
-        .join("exercises")
-        .join(exercise_name);
+    let problem_specifications_path = track_root.join("..").join("problem-specifications");
+
+    let exercise_path = track_root.join("exercises").join(exercise_name);
 
     if exercise_path.exists() {
-        panic!(
-            "Exercise with the name {} already exists. Aborting",
+        return Err(Error::human(format!(
+            "exercise with the name {} already exists",
             exercise_name
-        );
+        )));
     }
 
     println!(
@@ -146,63 +285,78 @@ fn generate_exercise(exercise_name: &str, run_configure: bool, use_maplit: bool)
         .arg("--lib")
         .arg(exercise_path.to_str().unwrap())
         .output()
-        .expect("Failed to generate a new exercise via 'cargo new' command");
+        .chain_err(|| "generating a new exercise via 'cargo new'")?;
 
     ::std::fs::write(exercise_path.join(".gitignore"), GITIGNORE_CONTENT)
-        .expect("Failed to create .gitignore file");
+        .chain_err(|| "creating .gitignore")?;
 
     if use_maplit {
         let mut cargo_toml_file = OpenOptions::new()
             .append(true)
             .open(exercise_path.join("Cargo.toml"))
-            .unwrap();
+            .chain_err(|| "opening Cargo.toml")?;
 
         cargo_toml_file
-            .write(b"maplit = \"1.0.1\"")
-            .expect("Failed to add maplit dependency to the Cargo.toml");
+            .write_all(b"maplit = \"1.0.1\"")
+            .chain_err(|| "adding the maplit dependency to Cargo.toml")?;
     }
 
     ::std::fs::create_dir(exercise_path.join("tests"))
-        .expect("Failed to create the tests directory");
+        .chain_err(|| "creating the tests directory")?;
 
     let mut test_file = File::create(
         exercise_path
             .join("tests")
             .join(format!("{}.rs", exercise_name)),
-    ).expect("Failed to create test suite file");
+    )
+    .chain_err(|| "creating the test suite file")?;
 
     if use_maplit {
-        test_file.write(b"#[macro_use]\nextern crate maplit;\n");
+        test_file
+            .write_all(b"#[macro_use]\nextern crate maplit;\n")
+            .chain_err(|| "writing the maplit import")?;
     }
 
     test_file
-        .write(&format!("extern crate {};\n", exercise_name.replace("-", "_")).into_bytes())
-        .unwrap();
+        .write_all(&format!("extern crate {};\n", exercise_name.replace("-", "_")).into_bytes())
+        .chain_err(|| "writing the crate import")?;
 
     test_file
-        .write(&format!("use {}::*;\n\n", exercise_name.replace("-", "_")).into_bytes())
-        .unwrap();
+        .write_all(&format!("use {}::*;\n\n", exercise_name.replace("-", "_")).into_bytes())
+        .chain_err(|| "writing the glob import")?;
 
     ::std::fs::write(exercise_path.join("example.rs"), EXAMPLE_RS_CONTENT)
-        .expect("Failed to create example.rs file");
-
-    if let Some(canonical_data) = get_canonical_data(exercise_name) {
-        println!("Generating tests from canonical data");
-
-        generate_tests_from_canonical_data(
-            &exercise_name,
-            &exercise_path,
-            &canonical_data,
-            use_maplit,
-        );
-    } else {
-        println!(
-            "No canonical data for exercise '{}' found. Generating standard exercise template.",
-            &exercise_name
-        );
-
-        generate_default_meta(&exercise_name, &exercise_path);
+        .chain_err(|| "creating example.rs")?;
+
+    let _ = run_configure;
+
+    match get_canonical_data(
+        exercise_name,
+        &problem_specifications_path,
+        &track_root,
+        offline,
+    )? {
+        Some(canonical_data) => {
+            println!("Generating tests from canonical data");
+
+            generate_tests_from_canonical_data(
+                &exercise_name,
+                &exercise_path,
+                &canonical_data,
+                use_maplit,
+            )?;
+        }
+        None => {
+            println!(
+                "No canonical data for exercise '{}' found. Generating standard exercise template.",
+                &exercise_name
+            );
+
+            generate_default_meta(&exercise_name, &exercise_path)?;
+        }
     }
+
+    Ok(())
 }
 
 pub fn process_matches(matches: &ArgMatches) {
@@ -212,5 +366,10 @@ pub fn process_matches(matches: &ArgMatches) {
 
     let use_maplit = matches.is_present("use_maplit");
 
-    generate_exercise(exercise_name, run_configure, use_maplit);
+    let offline = matches.is_present("offline");
+
+    if let Err(error) = generate_exercise(exercise_name, run_configure, use_maplit, offline) {
+        error.report();
+        ::std::process::exit(1);
+    }
 }
@@ -0,0 +1,108 @@
+/// Error handling for the `generate` command.
+///
+/// Modeled on the classic "human vs. internal" split: an [`Error`] carries an
+/// `is_human` flag separating ordinary, user-triggered failures (an exercise
+/// that already exists, an unreachable network, canonical data missing a
+/// required field) from genuine invariant violations. Human errors print a single clean line
+/// and exit non-zero; internal errors surface their full cause chain so a bug
+/// is never swallowed.
+use std::error::Error as StdError;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct Error {
+    message: String,
+    is_human: bool,
+    cause: Option<Box<dyn StdError + 'static>>,
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl Error {
+    /// A user-facing failure: printed as a single line, no backtrace.
+    pub fn human<S: Into<String>>(message: S) -> Error {
+        Error {
+            message: message.into(),
+            is_human: true,
+            cause: None,
+        }
+    }
+
+    /// An internal invariant violation: surfaces its whole cause chain.
+    pub fn internal<S: Into<String>>(message: S) -> Error {
+        Error {
+            message: message.into(),
+            is_human: false,
+            cause: None,
+        }
+    }
+
+    pub fn is_human(&self) -> bool {
+        self.is_human
+    }
+
+    /// Print the error to stderr, collapsing human errors to one line and
+    /// expanding internal ones into their full "caused by" chain.
+    pub fn report(&self) {
+        if self.is_human {
+            eprintln!("error: {}", self.message);
+            return;
+        }
+
+        eprintln!("error: {}", self.message);
+
+        let mut source = self.cause.as_ref().map(|c| c.as_ref());
+        while let Some(cause) = source {
+            eprintln!("  caused by: {}", cause);
+            source = cause.source();
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        if let Some(cause) = &self.cause {
+            write!(f, ": {}", cause)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.cause.as_ref().map(|c| c.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+/// Attach lazily-computed context to a failing `Result`, wrapping the
+/// underlying cause instead of discarding it.
+///
+/// ```ignore
+/// fetch().chain_err(|| "downloading canonical data")?;
+/// ```
+///
+/// A deep failure then bubbles up as a readable chain, e.g.
+/// `generating tests for bob: parsing case 3: property missing`.
+pub trait ChainErr<T> {
+    fn chain_err<F, S>(self, context: F) -> Result<T>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>;
+}
+
+impl<T, E: StdError + 'static> ChainErr<T> for ::std::result::Result<T, E> {
+    fn chain_err<F, S>(self, context: F) -> Result<T>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+    {
+        self.map_err(|cause| Error {
+            message: context().into(),
+            is_human: false,
+            cause: Some(Box::new(cause)),
+        })
+    }
+}
@@ -59,22 +59,16 @@ fn generate_default_meta(exercise_name: &str, exercise_path: &Path) -> Result<()
     Ok(())
 }
 
-// Generate test suite using the canonical data
-fn generate_tests_from_canonical_data(
+// Render the full `tests/<name>.rs` content (header, property bodies and test
+// functions) into a single string, without writing to the filesystem.
+fn render_tests_content(
     exercise_name: &str,
-    exercise_path: &Path,
     canonical_data: &JsonValue,
     use_maplit: bool,
-) -> Result<()> {
-    exercise::update_cargo_toml_version(exercise_name, canonical_data)?;
-
-    let tests_path = exercise_path
-        .join("tests")
-        .join(format!("{}.rs", exercise_name));
-
+) -> Result<String> {
     let tests_content = exercise::get_tests_content(exercise_name)?;
 
-    let updated_tests_content = format!(
+    let mut rendered = format!(
         "//! Tests for {exercise_name} \n\
         //! \n\
         //! Generated by [utility][utility] using [canonical data][canonical_data]\n\
@@ -88,12 +82,16 @@ fn generate_tests_from_canonical_data(
         exercise_name=exercise_name,
     );
 
-    fs::write(&tests_path, updated_tests_content)?;
-
     let mut property_functions: HashMap<&str, String> = HashMap::new();
 
     let mut test_functions: Vec<String> = Vec::new();
 
+    // A monotonically increasing counter shared across case groups so every
+    // case keys a distinct fixture file; a group-local index would collide
+    // between groups.
+    let mut case_index = 0;
+    let mut any_external = false;
+
     let cases = canonical_data
         .get("cases")
         .ok_or(format_err!("cases list not present in canonical data"))?;
@@ -120,7 +118,15 @@ fn generate_tests_from_canonical_data(
                     }
                 }
 
-                test_functions.push(exercise::generate_test_function(&sub_case, use_maplit)?);
+                if sub_case.get("external").is_some() {
+                    any_external = true;
+                    test_functions
+                        .push(render_external_test_function(sub_case, exercise_name, case_index)?);
+                } else {
+                    test_functions.push(exercise::generate_test_function(&sub_case, use_maplit)?);
+                }
+
+                case_index += 1;
             }
         } else {
             if let Some(property) = case.get("property") {
@@ -133,7 +139,15 @@ fn generate_tests_from_canonical_data(
                 }
             }
 
-            test_functions.push(exercise::generate_test_function(&case, use_maplit)?);
+            if case.get("external").is_some() {
+                any_external = true;
+                test_functions
+                    .push(render_external_test_function(case, exercise_name, case_index)?);
+            } else {
+                test_functions.push(exercise::generate_test_function(&case, use_maplit)?);
+            }
+
+            case_index += 1;
         }
     }
 
@@ -143,19 +157,515 @@ fn generate_tests_from_canonical_data(
         test_functions.insert(0, first_test_function);
     }
 
-    let mut tests_file = OpenOptions::new().append(true).open(&tests_path)?;
+    if any_external {
+        rendered.push_str(
+            "/// Read a case's input from its sibling fixture file.\n\
+            ///\n\
+            /// Cases whose input is marked `external` keep their (possibly megabyte-scale\n\
+            /// or binary) data in `tests/fixtures/<exercise>/<case_index>` instead of\n\
+            /// inlined in this file; this returns the raw bytes of that fixture.\n\
+            #[allow(dead_code)]\n\
+            fn read_example(exercise: &str, case_index: usize) -> Vec<u8> {\n\
+            \tlet path = std::path::Path::new(\"tests\")\n\
+            \t\t.join(\"fixtures\")\n\
+            \t\t.join(exercise)\n\
+            \t\t.join(case_index.to_string());\n\
+            \n\
+            \tstd::fs::read(&path)\n\
+            \t\t.unwrap_or_else(|e| panic!(\"failed to read fixture {}: {}\", path.display(), e))\n\
+            }\n\n",
+        );
+    }
 
     for (_, property_body) in &property_functions {
-        tests_file.write_all(property_body.as_bytes())?;
+        rendered.push_str(property_body);
+    }
+
+    rendered.push_str(&test_functions.join("\n\n"));
+
+    Ok(rendered)
+}
+
+// Render a `#[test]` function for a case whose input lives in a sibling fixture
+// file rather than inlined canonical data. The input expression is a
+// `read_example` call keyed by the exercise and the case's global index, so
+// oversized or binary inputs stay out of the `.rs` file; the expected value is
+// still inlined as JSON.
+fn render_external_test_function(
+    case: &JsonValue,
+    exercise_name: &str,
+    case_index: usize,
+) -> Result<String> {
+    let description = case
+        .get("description")
+        .and_then(|description| description.as_str())
+        .ok_or(format_err!("case missing description"))?;
+
+    let property = case
+        .get("property")
+        .and_then(|property| property.as_str())
+        .ok_or(format_err!("case missing property"))?;
+
+    let expected = serde_json::to_string(case.get("expected").unwrap_or(&JsonValue::Null))?;
+
+    Ok(format!(
+        "#[test]\n\
+        #[ignore]\n\
+        /// {description}\n\
+        fn {name}() {{\n\
+        \tlet input = read_example(\"{exercise_name}\", {case_index});\n\
+        \tprocess_{property}_case(input, {expected});\n\
+        }}",
+        description = description,
+        name = test_name(description),
+        exercise_name = exercise_name,
+        case_index = case_index,
+        property = property,
+        expected = expected,
+    ))
+}
+
+// Slugify a case description into a `test_`-prefixed Rust identifier, matching
+// the names the canonical-data helper emits for inlined cases.
+fn test_name(description: &str) -> String {
+    let mut slug = String::from("test_");
+    let mut last_underscore = true;
+
+    for ch in description.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.extend(ch.to_lowercase());
+            last_underscore = false;
+        } else if !last_underscore {
+            slug.push('_');
+            last_underscore = true;
+        }
     }
 
-    tests_file.write_all(test_functions.join("\n\n").as_bytes())?;
+    slug.truncate(slug.trim_end_matches('_').len());
+
+    slug
+}
+
+// Generate test suite using the canonical data.
+//
+// With `check` set the would-be content is rendered in memory and diffed
+// against the committed `tests/<name>.rs` instead of being written; the
+// function then returns whether the file is already up to date.
+fn generate_tests_from_canonical_data(
+    exercise_name: &str,
+    exercise_path: &Path,
+    canonical_data: &JsonValue,
+    use_maplit: bool,
+    check: bool,
+    max_line_width: usize,
+) -> Result<bool> {
+    let tests_path = exercise_path
+        .join("tests")
+        .join(format!("{}.rs", exercise_name));
+
+    let rendered = render_tests_content(exercise_name, canonical_data, use_maplit)?;
+
+    if check {
+        return check_against_committed(exercise_name, &tests_path, &rendered);
+    }
+
+    exercise::update_cargo_toml_version(exercise_name, canonical_data)?;
+
+    fs::write(&tests_path, &rendered)?;
 
     exercise::rustfmt(&tests_path)?;
 
+    enforce_line_width(&tests_path, max_line_width)?;
+
+    run_clippy(exercise_path, exercise_name)?;
+
+    generate_benches(exercise_name, exercise_path, canonical_data)?;
+
+    Ok(true)
+}
+
+// Flatten grouped and single canonical-data cases into one list.
+fn flatten_cases(canonical_data: &JsonValue) -> Result<Vec<JsonValue>> {
+    let cases = canonical_data
+        .get("cases")
+        .ok_or(format_err!("cases list not present in canonical data"))?
+        .as_array()
+        .ok_or(format_err!("case list inexpressable as array"))?;
+
+    let mut flat = Vec::new();
+
+    for case in cases {
+        if let Some(sub_cases) = case.get("cases") {
+            for sub_case in sub_cases
+                .as_array()
+                .ok_or(format_err!("subcase list inexpressable as array"))?
+            {
+                flat.push(sub_case.clone());
+            }
+        } else {
+            flat.push(case.clone());
+        }
+    }
+
+    Ok(flat)
+}
+
+// Render a Criterion benchmark harness for the exercise.
+//
+// Like the generated tests, the harness never inlines canonical-data inputs as
+// Rust literals: an object input is not valid Rust, and the solution's argument
+// list rarely matches a single positional JSON value. Instead each case's input
+// travels as a raw JSON string into a per-property `bench_<property>` shim
+// (mirroring the `process_<property>_case` shims in `property_fn.rs`) that the
+// exercise author fills in to decode the input and drive the solution under
+// `black_box`. The `parallel-letter-frequency` harness additionally sweeps the
+// worker count from 1 to the number of CPUs, which its shim takes as a leading
+// argument.
+fn render_bench_content(exercise_name: &str, canonical_data: &JsonValue) -> Result<String> {
+    let fn_name = exercise_name.replace("-", "_");
+
+    let cases = flatten_cases(canonical_data)?;
+
+    let group = cases
+        .first()
+        .and_then(|case| case.get("property"))
+        .and_then(|property| property.as_str())
+        .unwrap_or(exercise_name);
+
+    let sweep = exercise_name == "parallel-letter-frequency";
+
+    let mut content = format!(
+        "//! Benchmarks for {exercise_name}\n\
+        //!\n\
+        //! Generated by [utility][utility] using [canonical data][canonical_data]\n\
+        //!\n\
+        //! [utility]: https://github.com/exercism/rust/tree/master/util/exercise\n\
+        //! [canonical_data]: https://raw.githubusercontent.com/exercism/problem-specifications/master/exercises/{exercise_name}/canonical-data.json\n\
+        \n\
+        use criterion::{{black_box, criterion_group, criterion_main, Criterion}};\n\
+        \n",
+        exercise_name = exercise_name,
+    );
+
+    // One shim per distinct property, preserving first-seen order.
+    let mut properties: Vec<&str> = Vec::new();
+
+    for case in &cases {
+        if let Some(property) = case.get("property").and_then(|p| p.as_str()) {
+            if !properties.contains(&property) {
+                properties.push(property);
+            }
+        }
+    }
+
+    for property in &properties {
+        if sweep {
+            content.push_str(&format!(
+                "fn bench_{property}(worker_count: usize, input: &str) {{\n\
+                \t// `input` is the case's canonical-data input as raw JSON. Decode it\n\
+                \t// into the types the solution expects, then call the solution with\n\
+                \t// `worker_count` workers under `black_box`.\n\
+                \tunimplemented!(\n\
+                \t\t\"bench_{property} should decode {{:?}} and call the solution with {{}} workers\",\n\
+                \t\tinput,\n\
+                \t\tworker_count,\n\
+                \t);\n\
+                }}\n\n",
+                property = property,
+            ));
+        } else {
+            content.push_str(&format!(
+                "fn bench_{property}(input: &str) {{\n\
+                \t// `input` is the case's canonical-data input as raw JSON. Decode it\n\
+                \t// into the types the solution expects, then call the solution under\n\
+                \t// `black_box`.\n\
+                \tunimplemented!(\"bench_{property} should decode {{:?}} and call the solution\", input);\n\
+                }}\n\n",
+                property = property,
+            ));
+        }
+    }
+
+    content.push_str(&format!(
+        "fn {fn_name}_benchmark(c: &mut Criterion) {{\n\
+        \tlet mut group = c.benchmark_group(\"{group}\");\n\n",
+        fn_name = fn_name,
+        group = group,
+    ));
+
+    if sweep {
+        content.push_str("\tfor workers in 1..=num_cpus::get() {\n");
+    }
+
+    for case in &cases {
+        let description = case
+            .get("description")
+            .and_then(|description| description.as_str())
+            .unwrap_or("case");
+
+        let property = case
+            .get("property")
+            .and_then(|property| property.as_str())
+            .unwrap_or(group);
+
+        let input = serde_json::to_string(case.get("input").unwrap_or(&JsonValue::Null))?;
+        let input = raw_string_literal(&input);
+
+        if sweep {
+            content.push_str(&format!(
+                "\t\tgroup.bench_with_input(\n\
+                \t\t\tcriterion::BenchmarkId::new({description:?}, workers),\n\
+                \t\t\t&workers,\n\
+                \t\t\t|b, &workers| b.iter(|| bench_{property}(black_box(workers), black_box({input}))),\n\
+                \t\t);\n",
+                description = description,
+                property = property,
+                input = input,
+            ));
+        } else {
+            content.push_str(&format!(
+                "\tgroup.bench_function({description:?}, |b| {{\n\
+                \t\tb.iter(|| bench_{property}(black_box({input})))\n\
+                \t}});\n",
+                description = description,
+                property = property,
+                input = input,
+            ));
+        }
+    }
+
+    if sweep {
+        content.push_str("\t}\n");
+    }
+
+    content.push_str(&format!(
+        "\n\tgroup.finish();\n\
+        }}\n\
+        \n\
+        criterion_group!(benches, {fn_name}_benchmark);\n\
+        criterion_main!(benches);\n",
+        fn_name = fn_name,
+    ));
+
+    Ok(content)
+}
+
+// Wrap `s` in a raw string literal, widening the `#` fence past any run of
+// hashes the payload itself contains so JSON with embedded `"#` stays valid.
+fn raw_string_literal(s: &str) -> String {
+    let mut hashes = 1;
+
+    while s.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+
+    let fence = "#".repeat(hashes);
+
+    format!("r{fence}\"{s}\"{fence}", fence = fence, s = s)
+}
+
+// Write a `benches/<exercise>.rs` Criterion harness and register it (plus its
+// dev-dependencies) in the exercise's Cargo.toml.
+fn generate_benches(
+    exercise_name: &str,
+    exercise_path: &Path,
+    canonical_data: &JsonValue,
+) -> Result<()> {
+    let content = render_bench_content(exercise_name, canonical_data)?;
+
+    let benches_dir = exercise_path.join("benches");
+
+    fs::create_dir_all(&benches_dir)?;
+
+    let bench_path = benches_dir.join(format!("{}.rs", exercise_name));
+
+    fs::write(&bench_path, content)?;
+
+    exercise::rustfmt(&bench_path)?;
+
+    let mut cargo_toml = OpenOptions::new()
+        .append(true)
+        .open(exercise_path.join("Cargo.toml"))?;
+
+    let mut dev_deps = String::from(
+        "\n[dev-dependencies]\ncriterion = \"0.3\"\n",
+    );
+
+    if exercise_name == "parallel-letter-frequency" {
+        dev_deps.push_str("num_cpus = \"1.0\"\n");
+    }
+
+    dev_deps.push_str(&format!(
+        "\n[[bench]]\nname = \"{}\"\nharness = false\n",
+        exercise_name
+    ));
+
+    cargo_toml.write_all(dev_deps.as_bytes())?;
+
     Ok(())
 }
 
+// The default column limit applied to generated test suites. Matches the bar
+// hand-written track code is held to.
+const DEFAULT_MAX_LINE_WIDTH: usize = 100;
+
+// Fail generation if any emitted test or property-body line is wider than
+// `max_line_width` columns (e.g. a large maplit literal or nested match that
+// blew past the limit).
+fn enforce_line_width(tests_path: &Path, max_line_width: usize) -> Result<()> {
+    let content = fs::read_to_string(tests_path)?;
+
+    let offenders: Vec<String> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            // Exempt comment lines: the generated doc-comment header carries a
+            // long canonical-data URL and rustfmt never reflows comments.
+            if line.trim_start().starts_with("//") {
+                return None;
+            }
+
+            let width = line.chars().count();
+
+            if width > max_line_width {
+                Some(format!("line {} ({} cols)", index + 1, width))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if offenders.is_empty() {
+        return Ok(());
+    }
+
+    Err(format_err!(
+        "generated {} has lines exceeding {} columns: {}",
+        tests_path.display(),
+        max_line_width,
+        offenders.join(", ")
+    )
+    .into())
+}
+
+// Run clippy (denying warnings) over the freshly generated exercise crate. The
+// full diagnostics are echoed so lint regressions in the generated
+// `tests/<name>.rs` are visible, and a non-zero clippy status fails generation
+// rather than being silently swallowed.
+fn run_clippy(exercise_path: &Path, exercise_name: &str) -> Result<()> {
+    let output = Command::new("cargo")
+        .arg("clippy")
+        .arg("--tests")
+        .arg("--")
+        .arg("-D")
+        .arg("warnings")
+        .current_dir(exercise_path)
+        .stderr(Stdio::piped())
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    eprint!("{}", stderr);
+
+    Err(format_err!(
+        "clippy reported problems in the generated crate for '{}'",
+        exercise_name
+    )
+    .into())
+}
+
+// Format `rendered` exactly as generation would and compare it against the
+// committed tests file, printing a unified diff on mismatch. Returns `true`
+// when the committed file already matches.
+fn check_against_committed(
+    exercise_name: &str,
+    tests_path: &Path,
+    rendered: &str,
+) -> Result<bool> {
+    if !tests_path.exists() {
+        println!("{}: tests/{}.rs is missing", exercise_name, exercise_name);
+
+        return Ok(false);
+    }
+
+    // Run the candidate through rustfmt so the comparison is against the same
+    // formatting the committed file was written with. rustfmt only accepts
+    // `.rs` paths, so the scratch file keeps that extension.
+    let candidate_path = tests_path.with_file_name(format!("{}.__check__.rs", exercise_name));
+
+    fs::write(&candidate_path, rendered)?;
+
+    exercise::rustfmt(&candidate_path)?;
+
+    let expected = fs::read_to_string(&candidate_path)?;
+
+    fs::remove_file(&candidate_path)?;
+
+    let committed = fs::read_to_string(tests_path)?;
+
+    if committed == expected {
+        return Ok(true);
+    }
+
+    println!("{}: tests/{}.rs differs from regenerated output", exercise_name, exercise_name);
+
+    print_unified_diff(&committed, &expected);
+
+    Ok(false)
+}
+
+// Print a line-oriented unified diff between the committed file and the
+// regenerated output, aligning common lines via a longest-common-subsequence so
+// a single insertion or deletion doesn't desync the rest of the comparison.
+fn print_unified_diff(committed: &str, expected: &str) {
+    let committed_lines: Vec<&str> = committed.lines().collect();
+    let expected_lines: Vec<&str> = expected.lines().collect();
+
+    let rows = committed_lines.len();
+    let cols = expected_lines.len();
+
+    // lcs[i][j] = length of the LCS of committed[i..] and expected[j..].
+    let mut lcs = vec![vec![0usize; cols + 1]; rows + 1];
+
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lcs[i][j] = if committed_lines[i] == expected_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < rows && j < cols {
+        if committed_lines[i] == expected_lines[j] {
+            println!(" {}", committed_lines[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            println!("-{}", committed_lines[i]);
+            i += 1;
+        } else {
+            println!("+{}", expected_lines[j]);
+            j += 1;
+        }
+    }
+
+    for line in &committed_lines[i..] {
+        println!("-{}", line);
+    }
+
+    for line in &expected_lines[j..] {
+        println!("+{}", line);
+    }
+}
+
 // Run bin/configlet generate command to generate README for the exercise
 fn generate_readme(exercise_name: &str) -> Result<()> {
     println!(
@@ -200,7 +710,18 @@ fn generate_readme(exercise_name: &str) -> Result<()> {
 }
 
 // Generate a new exercise with specified name and flags
-pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
+pub fn generate_exercise(
+    exercise_name: &str,
+    use_maplit: bool,
+    check: bool,
+    max_line_width: Option<usize>,
+) -> Result<()> {
+    let max_line_width = max_line_width.unwrap_or(DEFAULT_MAX_LINE_WIDTH);
+
+    if check {
+        return check_exercises(exercise_name, use_maplit, max_line_width);
+    }
+
     if exercise::exercise_exists(exercise_name) {
         return Err(format_err!("exercise with the name {} already exists", exercise_name,).into());
     }
@@ -264,6 +785,8 @@ pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
                 &exercise_path,
                 &canonical_data,
                 use_maplit,
+                false,
+                max_line_width,
             )?;
         }
         Err(e) => {
@@ -281,3 +804,66 @@ pub fn generate_exercise(exercise_name: &str, use_maplit: bool) -> Result<()> {
 
     Ok(())
 }
+
+// Check a single exercise, or every exercise under `exercises/` when
+// `exercise_name` is empty or "all". Mismatches are accumulated and reported
+// together rather than aborting on the first one.
+fn check_exercises(exercise_name: &str, use_maplit: bool, max_line_width: usize) -> Result<()> {
+    let names = if exercise_name.is_empty() || exercise_name == "all" {
+        let exercises_dir = Path::new(&*exercise::TRACK_ROOT).join("exercises");
+
+        let mut names = Vec::new();
+
+        for entry in fs::read_dir(&exercises_dir)? {
+            let entry = entry?;
+
+            if entry.file_type()?.is_dir() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+
+        names.sort();
+        names
+    } else {
+        vec![exercise_name.to_string()]
+    };
+
+    let mut drifted = Vec::new();
+
+    for name in &names {
+        let exercise_path = Path::new(&*exercise::TRACK_ROOT).join("exercises").join(name);
+
+        let canonical_data = match exercise::get_canonical_data(name) {
+            Ok(canonical_data) => canonical_data,
+            Err(e) => {
+                eprintln!("Skipping '{}': failed to get canonical data: {}", name, e);
+
+                continue;
+            }
+        };
+
+        if !generate_tests_from_canonical_data(
+            name,
+            &exercise_path,
+            &canonical_data,
+            use_maplit,
+            true,
+            max_line_width,
+        )? {
+            drifted.push(name.clone());
+        }
+    }
+
+    if drifted.is_empty() {
+        println!("All checked test suites are up to date.");
+
+        Ok(())
+    } else {
+        Err(format_err!(
+            "{} test suite(s) differ from regenerated output: {}",
+            drifted.len(),
+            drifted.join(", ")
+        )
+        .into())
+    }
+}
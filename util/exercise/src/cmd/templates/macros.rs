@@ -0,0 +1,50 @@
+{# Helpers shared by the generated test-file templates. #}
+
+{# Turn a canonical-data case description into a valid Rust identifier.
+
+   When `use_test_prefix` is true the name keeps the historical `test_` prefix;
+   otherwise the prefix is stripped so the generated name matches the track's
+   current unprefixed convention (e.g. `two_of_the_same_book` rather than
+   `test_two_of_the_same_book`). Identifiers that would start with a digit are
+   guarded with a leading underscore, and one that would be empty becomes
+   `_case`, so the output always parses. #}
+{% macro slug(description, use_test_prefix=true) -%}
+    {%- set base = description | slugify | replace(from="-", to="_") -%}
+    {%- if use_test_prefix -%}
+        {%- set base = "test_" ~ base -%}
+    {%- else -%}
+        {%- if base is starting_with("test_") -%}
+            {%- set base = base | trim_start_matches(pat="test_") -%}
+        {%- endif -%}
+    {%- endif -%}
+    {%- if base == "" -%}
+        _case
+    {%- elif base is matching("^[0-9]") -%}
+        _{{ base }}
+    {%- else -%}
+        {{ base }}
+    {%- endif -%}
+{%- endmacro slug %}
+
+{# Emit a single `#[test]` function for a canonical-data case.
+
+   `name` is the already-slugified, collision-disambiguated identifier computed
+   by the caller. `dont_ignore` leaves the very first generated test
+   un-`#[ignore]`d so the suite compiles and runs out of the box. `exercise` and
+   `index` let a case pull its input from a sibling fixture file instead of
+   inlining it: when `case.external` is set the input expression becomes a
+   `read_example` call keyed by the exercise and this case's index, so giant or
+   binary inputs stay out of the `.rs` file. #}
+{% macro gen_test_fn(case, dont_ignore, exercise, index, name) -%}
+{% if not dont_ignore %}#[ignore]
+{% endif -%}
+#[test]
+fn {{ name }}() {
+    {% if case.external -%}
+    let input = read_example("{{ exercise }}", {{ index }});
+    process_{{ case.property }}_case(input, {{ case.expected | json_encode() }});
+    {%- else -%}
+    process_{{ case.property }}_case({{ case.input | json_encode() }}, {{ case.expected | json_encode() }});
+    {%- endif %}
+}
+{%- endmacro gen_test_fn %}
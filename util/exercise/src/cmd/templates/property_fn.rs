@@ -0,0 +1,11 @@
+
+fn process_{{ property }}_case<Input, Expected>(input: Input, expected: Expected) {
+    // Replace the parameter and return types above with the concrete types used
+    // by this exercise, then call the solution with `input` and assert the
+    // result equals `expected`.
+    unimplemented!(
+        "process_{{ property }}_case should call the solution with {:?} and assert it equals {:?}",
+        input,
+        expected
+    );
+}
@@ -15,10 +15,38 @@
 use maplit::hashmap;
 {% endif -%}
 
+/// Read a case's input from its sibling fixture file.
+///
+/// Cases whose input is marked `external` keep their (possibly megabyte-scale or
+/// binary) data in `tests/fixtures/<exercise>/<case_index>` instead of inlined
+/// in this file; this returns the raw bytes of that fixture.
+#[allow(dead_code)]
+fn read_example(exercise: &str, case_index: usize) -> Vec<u8> {
+    let path = std::path::Path::new("tests")
+        .join("fixtures")
+        .join(exercise)
+        .join(case_index.to_string());
+
+    std::fs::read(&path)
+        .unwrap_or_else(|e| panic!("failed to read fixture {}: {}", path.display(), e))
+}
+
 {# Prepare an array (global) to store the properties. Also, don't ignore the first case. -#}
 {% set properties = [] -%}
 {% set dont_ignore = true -%}
 
+{# A monotonically increasing case counter, so fixtures stay uniquely keyed even
+   across case groups (a group-local index would collide between groups). -#}
+{% set case_index = 0 -%}
+
+{# Track the identifiers already emitted so descriptions that collide after
+   slugification get a unique suffix instead of producing duplicate `fn` names. -#}
+{% set used_names = [] -%}
+
+{# Generate unprefixed test names (`two_of_the_same_book`) unless the caller
+   opts back into the historical `test_` prefix. -#}
+{% set use_test_prefix = use_test_prefix | default(value=false) -%}
+
 {% for item in cases -%}
     {# Check if we're dealing with a group of cases. #}
     {% if item.cases -%}
@@ -35,14 +63,22 @@ use maplit::hashmap;
 
         {% for case in item.cases -%}
             {% set_global properties = properties | concat(with=case.property) -%}
-            {{ macros::gen_test_fn(case=case, dont_ignore=dont_ignore) }}
+            {% set base = macros::slug(description=case.description, use_test_prefix=use_test_prefix) -%}
+            {% if base in used_names -%}{% set name = base ~ "_" ~ case_index -%}{% else -%}{% set name = base -%}{% endif -%}
+            {% set_global used_names = used_names | concat(with=name) -%}
+            {{ macros::gen_test_fn(case=case, dont_ignore=dont_ignore, exercise=exercise, index=case_index, name=name) }}
+            {% set_global case_index = case_index + 1 -%}
             {% set_global dont_ignore = false -%}
         {% endfor -%}
 
     {# Or just a single one. #}
     {% else -%}
         {% set_global properties = properties | concat(with=item.property) -%}
-        {{ macros::gen_test_fn(case=item, dont_ignore=dont_ignore) }}
+        {% set base = macros::slug(description=item.description, use_test_prefix=use_test_prefix) -%}
+        {% if base in used_names -%}{% set name = base ~ "_" ~ case_index -%}{% else -%}{% set name = base -%}{% endif -%}
+        {% set_global used_names = used_names | concat(with=name) -%}
+        {{ macros::gen_test_fn(case=item, dont_ignore=dont_ignore, exercise=exercise, index=case_index, name=name) }}
+        {% set_global case_index = case_index + 1 -%}
         {% set_global dont_ignore = false -%}
     {% endif -%}
 {% endfor -%}